@@ -18,9 +18,8 @@ pub struct CVRFDocument {
     #[serde(rename = "DocumentNotes")]
     pub document_notes: Vec<DocumentNote>,
 
-    // We skip deserializing this field since Microsoft fucks the structure every once in a while
-    #[serde(rename = "ProductTree", skip)]
-    pub product_tree: String,
+    #[serde(rename = "ProductTree", default)]
+    pub product_tree: ProductTree,
 
     #[serde(rename = "Vulnerability")]
     pub vulnerability: Vec<Vulnerability>,
@@ -119,48 +118,17 @@ pub struct Note {
     pub value: Option<String>,
 }
 
-#[derive(Deserialize)]
+// `Branch` nests two more levels (`Item`, `ProductIDValue`) that mirror
+// MSRC's internal product grouping but aren't read anywhere in this crate —
+// only `FullProductName` is. Modeling them strictly would just be crash
+// surface for a shape we never use, so we only deserialize the field we
+// need and default it away entirely if MSRC ever reshapes `ProductTree`.
+#[derive(Deserialize, Default)]
 pub struct ProductTree {
-    #[serde(rename = "Branch")]
-    pub branch: Vec<Branch>,
-
-    #[serde(rename = "FullProductName")]
+    #[serde(rename = "FullProductName", default)]
     pub full_product_name: Vec<FullProductName>,
 }
 
-#[derive(Deserialize)]
-pub struct Branch {
-    #[serde(rename = "Items")]
-    pub items: Vec<Item>,
-
-    #[serde(rename = "Type")]
-    pub type_: i32,
-
-    #[serde(rename = "Name")]
-    pub name: String,
-}
-
-#[derive(Deserialize)]
-pub struct Item {
-    #[serde(rename = "Items")]
-    pub items: Vec<ProductIDValue>,
-
-    #[serde(rename = "Type")]
-    pub type_: i32,
-
-    #[serde(rename = "Name")]
-    pub name: String,
-}
-
-#[derive(Deserialize)]
-pub struct ProductIDValue {
-    #[serde(rename = "ProductID")]
-    pub product_id: String,
-
-    #[serde(rename = "Value")]
-    pub value: String,
-}
-
 #[derive(Deserialize)]
 pub struct FullProductName {
     #[serde(rename = "ProductID")]
@@ -215,7 +183,26 @@ pub struct ProductStatus {
     pub product_id: Option<Vec<String>>,
 
     #[serde(rename = "Type")]
-    pub type_: i32,
+    pub type_: ProductStatusType,
+}
+
+/// The CVRF `ProductStatus.Type` discriminator. MSRC only documents `3` as
+/// "Known Affected"; anything else is kept around rather than discarded so a
+/// schema change on their end doesn't crash deserialization.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "i32")]
+pub enum ProductStatusType {
+    KnownAffected,
+    Unknown(i32),
+}
+
+impl From<i32> for ProductStatusType {
+    fn from(value: i32) -> Self {
+        match value {
+            3 => Self::KnownAffected,
+            other => Self::Unknown(other),
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -227,12 +214,35 @@ pub struct Threat {
     pub product_id: Option<Vec<String>>,
 
     #[serde(rename = "Type")]
-    pub type_: i32,
+    pub type_: ThreatType,
 
     #[serde(rename = "DateSpecified")]
     pub date_specified: bool,
 }
 
+/// The CVRF `Threat.Type` discriminator: `0` is impact, `1` is exploitability,
+/// `3` is severity. Unrecognized values are kept as `Unknown` instead of
+/// failing deserialization.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(from = "i32")]
+pub enum ThreatType {
+    Impact,
+    Exploitability,
+    Severity,
+    Unknown(i32),
+}
+
+impl From<i32> for ThreatType {
+    fn from(value: i32) -> Self {
+        match value {
+            0 => Self::Impact,
+            1 => Self::Exploitability,
+            3 => Self::Severity,
+            other => Self::Unknown(other),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 pub struct CVSSScoreSet {
     #[serde(rename = "BaseScore")]
@@ -0,0 +1,268 @@
+// Parses CVSS v3.1 vector strings (the `Vector` field MSRC ships alongside
+// `BaseScore`) into typed metrics, and recomputes the base score as a
+// correctness check against the document's own value.
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{fmt, str};
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum AttackVector {
+    Network,
+    Adjacent,
+    Local,
+    Physical,
+}
+
+impl str::FromStr for AttackVector {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ValueEnum::from_str(s, true)
+    }
+}
+
+impl fmt::Display for AttackVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Network => "Network",
+                Self::Adjacent => "Adjacent",
+                Self::Local => "Local",
+                Self::Physical => "Physical",
+            }
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum AttackComplexity {
+    Low,
+    High,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum PrivilegesRequired {
+    None,
+    Low,
+    High,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum UserInteraction {
+    None,
+    Required,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum Scope {
+    Unchanged,
+    Changed,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize)]
+pub enum Impact {
+    None,
+    Low,
+    High,
+}
+
+/// A parsed CVSS 3.1 base vector, e.g. `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`.
+#[derive(Clone, Debug, Serialize)]
+pub struct CvssVector {
+    pub attack_vector: AttackVector,
+    pub attack_complexity: AttackComplexity,
+    pub privileges_required: PrivilegesRequired,
+    pub user_interaction: UserInteraction,
+    pub scope: Scope,
+    pub confidentiality: Impact,
+    pub integrity: Impact,
+    pub availability: Impact,
+}
+
+impl str::FromStr for CvssVector {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = s.split('/');
+        match fields.next() {
+            Some(prefix) if prefix.starts_with("CVSS:3.") => {}
+            _ => return Err(format!("unsupported CVSS vector prefix in {s:?}")),
+        }
+
+        let mut attack_vector = None;
+        let mut attack_complexity = None;
+        let mut privileges_required = None;
+        let mut user_interaction = None;
+        let mut scope = None;
+        let mut confidentiality = None;
+        let mut integrity = None;
+        let mut availability = None;
+
+        for field in fields {
+            let (key, value) = field
+                .split_once(':')
+                .ok_or_else(|| format!("malformed CVSS metric {field:?}"))?;
+            match key {
+                "AV" => {
+                    attack_vector = Some(match value {
+                        "N" => AttackVector::Network,
+                        "A" => AttackVector::Adjacent,
+                        "L" => AttackVector::Local,
+                        "P" => AttackVector::Physical,
+                        _ => return Err(format!("unknown AV value {value:?}")),
+                    })
+                }
+                "AC" => {
+                    attack_complexity = Some(match value {
+                        "L" => AttackComplexity::Low,
+                        "H" => AttackComplexity::High,
+                        _ => return Err(format!("unknown AC value {value:?}")),
+                    })
+                }
+                "PR" => {
+                    privileges_required = Some(match value {
+                        "N" => PrivilegesRequired::None,
+                        "L" => PrivilegesRequired::Low,
+                        "H" => PrivilegesRequired::High,
+                        _ => return Err(format!("unknown PR value {value:?}")),
+                    })
+                }
+                "UI" => {
+                    user_interaction = Some(match value {
+                        "N" => UserInteraction::None,
+                        "R" => UserInteraction::Required,
+                        _ => return Err(format!("unknown UI value {value:?}")),
+                    })
+                }
+                "S" => {
+                    scope = Some(match value {
+                        "U" => Scope::Unchanged,
+                        "C" => Scope::Changed,
+                        _ => return Err(format!("unknown S value {value:?}")),
+                    })
+                }
+                "C" => confidentiality = Some(parse_impact(value)?),
+                "I" => integrity = Some(parse_impact(value)?),
+                "A" => availability = Some(parse_impact(value)?),
+                _ => {} // ignore temporal/environmental metrics we don't model
+            }
+        }
+
+        Ok(CvssVector {
+            attack_vector: attack_vector.ok_or("missing mandatory AV metric")?,
+            attack_complexity: attack_complexity.ok_or("missing mandatory AC metric")?,
+            privileges_required: privileges_required.ok_or("missing mandatory PR metric")?,
+            user_interaction: user_interaction.ok_or("missing mandatory UI metric")?,
+            scope: scope.ok_or("missing mandatory S metric")?,
+            confidentiality: confidentiality.ok_or("missing mandatory C metric")?,
+            integrity: integrity.ok_or("missing mandatory I metric")?,
+            availability: availability.ok_or("missing mandatory A metric")?,
+        })
+    }
+}
+
+fn parse_impact(value: &str) -> Result<Impact, String> {
+    match value {
+        "N" => Ok(Impact::None),
+        "L" => Ok(Impact::Low),
+        "H" => Ok(Impact::High),
+        _ => Err(format!("unknown impact value {value:?}")),
+    }
+}
+
+fn impact_weight(impact: Impact) -> f64 {
+    match impact {
+        Impact::None => 0.0,
+        Impact::Low => 0.22,
+        Impact::High => 0.56,
+    }
+}
+
+/// CVSS's "roundup" function: round up to the nearest 0.1.
+fn roundup(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() / 100_000.0;
+    (scaled * 10.0).ceil() / 10.0
+}
+
+impl CvssVector {
+    /// Recomputes the CVSS 3.1 base score from the parsed metrics, per the
+    /// official specification, so callers can check it against the
+    /// document's own `base_score`.
+    pub fn base_score(&self) -> f64 {
+        let c = impact_weight(self.confidentiality);
+        let i = impact_weight(self.integrity);
+        let a = impact_weight(self.availability);
+        let iss = 1.0 - ((1.0 - c) * (1.0 - i) * (1.0 - a));
+
+        let impact = if self.scope == Scope::Unchanged {
+            6.42 * iss
+        } else {
+            7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powf(15.0)
+        };
+
+        if impact <= 0.0 {
+            return 0.0;
+        }
+
+        let av = match self.attack_vector {
+            AttackVector::Network => 0.85,
+            AttackVector::Adjacent => 0.62,
+            AttackVector::Local => 0.55,
+            AttackVector::Physical => 0.2,
+        };
+        let ac = match self.attack_complexity {
+            AttackComplexity::Low => 0.77,
+            AttackComplexity::High => 0.44,
+        };
+        let pr = match (self.privileges_required, self.scope) {
+            (PrivilegesRequired::None, _) => 0.85,
+            (PrivilegesRequired::Low, Scope::Unchanged) => 0.62,
+            (PrivilegesRequired::Low, Scope::Changed) => 0.68,
+            (PrivilegesRequired::High, Scope::Unchanged) => 0.27,
+            (PrivilegesRequired::High, Scope::Changed) => 0.5,
+        };
+        let ui = match self.user_interaction {
+            UserInteraction::None => 0.85,
+            UserInteraction::Required => 0.62,
+        };
+        let exploitability = 8.22 * av * ac * pr * ui;
+
+        if self.scope == Scope::Unchanged {
+            roundup((impact + exploitability).min(10.0))
+        } else {
+            roundup((1.08 * (impact + exploitability)).min(10.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base_score_matches_known_vector_scope_unchanged() {
+        let vector: CvssVector = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".parse().unwrap();
+        assert_eq!(vector.base_score(), 9.8);
+    }
+
+    #[test]
+    fn base_score_matches_known_vector_scope_changed() {
+        let vector: CvssVector = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:C/C:H/I:H/A:H".parse().unwrap();
+        assert_eq!(vector.base_score(), 10.0);
+    }
+
+    #[test]
+    fn from_str_rejects_missing_mandatory_metric() {
+        assert!("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H"
+            .parse::<CvssVector>()
+            .is_err());
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_prefix() {
+        assert!("AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+            .parse::<CvssVector>()
+            .is_err());
+    }
+}
@@ -1,8 +1,12 @@
 pub mod cvrf;
+pub mod cvss;
+pub mod nvd;
+pub mod output;
 use clap::ValueEnum;
+use serde::Serialize;
 use std::{fmt, str};
 
-#[derive(clap::ValueEnum, Clone, PartialEq)]
+#[derive(Clone, PartialEq, Serialize)]
 pub enum Severity {
     Critical,
     Important,
@@ -11,12 +15,47 @@ pub enum Severity {
     Medium,
     Low,
     None,
+    /// A severity value CVRF documents don't define, kept instead of
+    /// aborting the whole parse, mirroring the "unknown value" pattern
+    /// generated Azure API bindings use for forward-compatibility.
+    Unknown(String),
+}
+
+// Implemented by hand rather than derived: `Unknown(String)` can't be
+// enumerated as a CLI possible value, so `--severity` only ever offers the
+// variants MSRC documents.
+impl ValueEnum for Severity {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Self::Critical,
+            Self::Important,
+            Self::Moderate,
+            Self::High,
+            Self::Medium,
+            Self::Low,
+            Self::None,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<clap::builder::PossibleValue> {
+        let token = match self {
+            Self::Critical => "critical",
+            Self::Important => "important",
+            Self::Moderate => "moderate",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+            Self::None => "none",
+            Self::Unknown(_) => return None,
+        };
+        Some(clap::builder::PossibleValue::new(token))
+    }
 }
 
 impl str::FromStr for Severity {
-    type Err = String;
+    type Err = std::convert::Infallible;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        ValueEnum::from_str(s, true)
+        Ok(ValueEnum::from_str(s, true).unwrap_or_else(|_| Severity::Unknown(s.to_owned())))
     }
 }
 
@@ -33,6 +72,7 @@ impl fmt::Display for Severity {
                 Self::Medium => "Medium",
                 Self::Low => "Low",
                 Self::None => "",
+                Self::Unknown(value) => value,
             }
         )
     }
@@ -47,26 +87,75 @@ pub enum Impact {
     Spoofing,
 }
 
-#[allow(non_camel_case_types)]
-#[derive(ValueEnum, Clone, Copy, PartialEq, Debug)]
-pub enum Product {
-    All,
-    Win10_1809_x64 = 11569,
-    Win11_22H2_x64 = 12086,
-} // Add more products as necessary :)
+/// Maps every `ProductID` in a CVRF document's `ProductTree` to its
+/// human-readable name, so products can be matched by name instead of a
+/// hardcoded enum of magic IDs.
+pub fn product_lookup(product_tree: &cvrf::ProductTree) -> std::collections::HashMap<String, String> {
+    product_tree
+        .full_product_name
+        .iter()
+        .map(|product| (product.product_id.clone(), product.value.clone()))
+        .collect()
+}
 
-impl str::FromStr for Product {
-    type Err = &'static str;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "11569" => Ok(Product::Win10_1809_x64),
-            "12086" => Ok(Product::Win11_22H2_x64),
-            _ => Err("Invalid product"),
+/// Extracts the (build, revision) pair — the last two dot-separated numeric
+/// components — from a Windows build string. MSRC's `FixedBuild` values are
+/// fully qualified (`10.0.19045.4046`) while a user-supplied `--min-build`
+/// often isn't (`19045.4046`); comparing only this common tail keeps both
+/// forms ordered consistently instead of comparing mismatched-length
+/// component vectors lexicographically.
+fn parse_build_number(build: &str) -> (u64, u64) {
+    let mut parts = build
+        .split('.')
+        .filter_map(|part| part.parse::<u64>().ok())
+        .rev();
+    let revision = parts.next().unwrap_or(0);
+    let build_number = parts.next().unwrap_or(0);
+    (build_number, revision)
+}
+
+/// Compares two Windows build strings by their (build, revision) tail, e.g.
+/// `10.0.19045.4046` vs. `19045.3570`.
+pub fn compare_builds(a: &str, b: &str) -> std::cmp::Ordering {
+    parse_build_number(a).cmp(&parse_build_number(b))
+}
+
+/// A KB article and fixed build, summarized from a CVRF `Remediation` and
+/// tagged with the `ProductID`s that remediation applies to.
+#[derive(Clone, Serialize)]
+pub struct RemediationSummary {
+    pub kb: Option<String>,
+    pub fixed_build: Option<String>,
+    pub restart_required: bool,
+    pub sub_type: Option<String>,
+    #[serde(skip)]
+    product_ids: Vec<String>,
+}
+
+impl From<&cvrf::Remediation> for RemediationSummary {
+    fn from(remediation: &cvrf::Remediation) -> Self {
+        let kb = remediation.url.as_ref().and_then(|url| {
+            url.rsplit('/')
+                .find(|segment| !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))
+                .map(|segment| segment.to_owned())
+        });
+        let restart_required = remediation
+            .restart_required
+            .as_ref()
+            .and_then(|field| field.value.clone())
+            .is_some_and(|value| value.eq_ignore_ascii_case("yes"));
+
+        RemediationSummary {
+            kb,
+            fixed_build: remediation.fixed_build.clone(),
+            restart_required,
+            sub_type: remediation.sub_type.clone(),
+            product_ids: remediation.product_id.clone().unwrap_or_default(),
         }
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize)]
 pub struct Vulnerability {
     pub title: String,
     pub cve: String,
@@ -78,30 +167,74 @@ pub struct Vulnerability {
     pub public: bool,
     pub exploited: bool,
     pub affected_products: Vec<String>,
+    pub cvss_vector: Option<cvss::CvssVector>,
+    /// Notes about fields that couldn't be parsed cleanly from the document
+    /// (e.g. a missing threat note or a diverging CVSS score), so one
+    /// malformed entry doesn't abort the whole run.
+    pub parse_warnings: Vec<String>,
+    /// CWE identifiers, filled in by `nvd::enrich` when `--enrich-nvd` is passed.
+    pub cwe: Vec<String>,
+    /// Reference URLs, filled in by `nvd::enrich` when `--enrich-nvd` is passed.
+    pub references: Vec<String>,
+    /// NVD's own CVSS vector, filled in by `nvd::enrich`; may disagree with `cvss`.
+    pub nvd_cvss_vector: Option<String>,
+    /// NVD's authoritative `published` timestamp, filled in by `nvd::enrich`.
+    pub nvd_published: Option<String>,
+    /// NVD's authoritative `lastModified` timestamp, filled in by `nvd::enrich`.
+    pub nvd_last_modified: Option<String>,
+    /// KB articles and fixed builds for this CVE, narrowed to the selected
+    /// product by `scope_remediations`.
+    pub remediations: Vec<RemediationSummary>,
 }
 
 impl From<&cvrf::Vulnerability> for Vulnerability {
     fn from(item: &cvrf::Vulnerability) -> Self {
         let title = item.title.value.clone().unwrap_or_default();
         let cve = item.cve.clone();
-        let severity = item
-            .threats
-            .iter()
-            .find(|threat| threat.type_ == 3)
-            .and_then(|note| note.description.clone().unwrap().value)
-            .unwrap_or("None".to_owned())
-            .parse()
-            .unwrap();
+        let mut parse_warnings = Vec::new();
+
+        let threat_description = |type_: cvrf::ThreatType| -> Option<String> {
+            let threat = item.threats.iter().find(|threat| threat.type_ == type_)?;
+            match &threat.description {
+                Some(description) => description.value.clone(),
+                None => None,
+            }
+        };
+
+        let severity_text = threat_description(cvrf::ThreatType::Severity);
+        if severity_text.is_none() {
+            parse_warnings.push(format!("{cve}: no severity threat found, defaulting to None"));
+        }
+        let severity_text = severity_text.unwrap_or("None".to_owned());
+        let severity = match severity_text.parse::<Severity>() {
+            Ok(severity) => severity,
+            Err(e) => match e {},
+        };
+
         let cvss = item
             .cvss_score_sets
             .get(0)
             .and_then(|cvss_set| Some(cvss_set.base_score));
-        let impact = item
-            .threats
-            .iter()
-            .find(|threat| threat.type_ == 0)
-            .and_then(|note| note.description.clone().unwrap().value)
-            .unwrap_or_default();
+        let cvss_vector = item.cvss_score_sets.get(0).and_then(|cvss_set| {
+            match cvss_set.vector.parse::<cvss::CvssVector>() {
+                Ok(vector) => Some(vector),
+                Err(e) => {
+                    parse_warnings.push(format!("{cve}: failed to parse CVSS vector: {e}"));
+                    None
+                }
+            }
+        });
+        if let (Some(vector), Some(base_score)) = (&cvss_vector, cvss) {
+            let recomputed = vector.base_score();
+            if (recomputed - base_score).abs() > 0.1 {
+                parse_warnings.push(format!(
+                    "{cve}: recomputed CVSS base score {recomputed} diverges from document's {base_score}"
+                ));
+            }
+        }
+
+        let impact = threat_description(cvrf::ThreatType::Impact).unwrap_or_default();
+
         let description = item
             .notes
             .iter()
@@ -113,22 +246,29 @@ impl From<&cvrf::Vulnerability> for Vulnerability {
             .flat_map(|ack| ack.name.iter())
             .map(|field| field.value.clone())
             .collect();
-        let vuln_exploitability = item
-            .threats
-            .iter()
-            .find(|threat| threat.type_ == 1)
-            .and_then(|note| note.description.clone().unwrap().value)
-            .unwrap_or_default();
+
+        let vuln_exploitability =
+            threat_description(cvrf::ThreatType::Exploitability).unwrap_or_default();
         let exploitability_fields: Vec<&str> = vuln_exploitability.split(';').collect();
-        // println!("{:#?}", exploitability_fields); todo! some only have "DOS:N/A"
         let public = exploitability_fields.get(0).unwrap_or(&"").contains("Yes");
         let exploited = exploitability_fields.get(1).unwrap_or(&"").contains("Yes");
+
         let affected_products = item
             .product_statuses
             .iter()
-            .find(|product_status| product_status.type_ == 3)
+            .find(|product_status| product_status.type_ == cvrf::ProductStatusType::KnownAffected)
             .and_then(|product_status| product_status.product_id.clone())
-            .unwrap_or_default();
+            .unwrap_or_else(|| {
+                parse_warnings.push(format!("{cve}: no known-affected product status found"));
+                Vec::new()
+            });
+
+        let remediations = item
+            .remediations
+            .iter()
+            .map(RemediationSummary::from)
+            .collect();
+
         Vulnerability {
             title,
             cve,
@@ -140,7 +280,45 @@ impl From<&cvrf::Vulnerability> for Vulnerability {
             public,
             exploited,
             affected_products,
+            cvss_vector,
+            parse_warnings,
+            cwe: Vec::new(),
+            references: Vec::new(),
+            nvd_cvss_vector: None,
+            nvd_published: None,
+            nvd_last_modified: None,
+            remediations,
+        }
+    }
+}
+
+impl Vulnerability {
+    /// Replaces each entry of `affected_products` (a `ProductID`) with its
+    /// human-readable name from `lookup`, falling back to the raw ID when
+    /// it isn't found.
+    pub fn resolve_products(&mut self, lookup: &std::collections::HashMap<String, String>) {
+        self.affected_products = self
+            .affected_products
+            .iter()
+            .map(|id| lookup.get(id).cloned().unwrap_or_else(|| id.clone()))
+            .collect();
+    }
+
+    /// Keeps only remediations scoped to one of `product_ids` (or with no
+    /// product restriction at all), so the output matches what the selected
+    /// product actually needs. A no-op when `product_ids` is empty (no
+    /// product selected).
+    pub fn scope_remediations(&mut self, product_ids: &[String]) {
+        if product_ids.is_empty() {
+            return;
         }
+        self.remediations.retain(|remediation| {
+            remediation.product_ids.is_empty()
+                || remediation
+                    .product_ids
+                    .iter()
+                    .any(|id| product_ids.contains(id))
+        });
     }
 }
 
@@ -153,6 +331,9 @@ impl fmt::Display for Vulnerability {
             write!(f, "CVSS: {cvss}\n")?;
         }
         write!(f, "Impact: {}\n", self.impact)?;
+        if !self.affected_products.is_empty() {
+            write!(f, "Affected Products: {}\n", self.affected_products.join(", "))?;
+        }
         if let Some(description) = &self.description {
             write!(f, "Description: {description}\n")?;
         }
@@ -161,7 +342,93 @@ impl fmt::Display for Vulnerability {
         if let Some(acknowledgements) = &self.acknowledgements {
             write!(f, "Acknowledgments: {acknowledgements}\n")?;
         }
+        if let Some(nvd_cvss_vector) = &self.nvd_cvss_vector {
+            write!(f, "NVD CVSS Vector: {nvd_cvss_vector}\n")?;
+        }
+        if let Some(nvd_published) = &self.nvd_published {
+            write!(f, "NVD Published: {nvd_published}\n")?;
+        }
+        if let Some(nvd_last_modified) = &self.nvd_last_modified {
+            write!(f, "NVD Last Modified: {nvd_last_modified}\n")?;
+        }
+        if !self.cwe.is_empty() {
+            write!(f, "CWE: {}\n", self.cwe.join(", "))?;
+        }
+        if !self.references.is_empty() {
+            write!(f, "References:\n")?;
+            for reference in &self.references {
+                write!(f, "  {reference}\n")?;
+            }
+        }
+        if !self.remediations.is_empty() {
+            write!(f, "Remediations:\n")?;
+            for remediation in &self.remediations {
+                match &remediation.kb {
+                    Some(kb) => write!(f, "  KB{kb}")?,
+                    None => write!(f, "  (no KB)")?,
+                }
+                if let Some(fixed_build) = &remediation.fixed_build {
+                    write!(f, ", Fixed Build: {fixed_build}")?;
+                }
+                write!(f, ", Restart Required: {}\n", remediation.restart_required)?;
+            }
+        }
+        if !self.parse_warnings.is_empty() {
+            write!(f, "Parse Warnings:\n")?;
+            for warning in &self.parse_warnings {
+                write!(f, "  {warning}\n")?;
+            }
+        }
 
         write!(f, "{}", "-".repeat(8))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_builds_orders_by_build_then_revision() {
+        assert_eq!(
+            compare_builds("10.0.19045.4046", "19045.3570"),
+            std::cmp::Ordering::Greater
+        );
+        assert_eq!(
+            compare_builds("10.0.19045.3570", "10.0.19045.4046"),
+            std::cmp::Ordering::Less
+        );
+        assert_eq!(
+            compare_builds("10.0.19045.4046", "19045.4046"),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    fn remediation(url: Option<&str>) -> cvrf::Remediation {
+        cvrf::Remediation {
+            description: cvrf::ValueField { value: None },
+            url: url.map(|url| url.to_owned()),
+            product_id: None,
+            type_: 0,
+            date_specified: false,
+            affected_files: Vec::new(),
+            restart_required: None,
+            sub_type: None,
+            fixed_build: None,
+        }
+    }
+
+    #[test]
+    fn remediation_summary_extracts_kb_from_url() {
+        let summary = RemediationSummary::from(&remediation(Some(
+            "https://support.microsoft.com/help/5005565",
+        )));
+        assert_eq!(summary.kb.as_deref(), Some("5005565"));
+    }
+
+    #[test]
+    fn remediation_summary_kb_is_none_without_url() {
+        let summary = RemediationSummary::from(&remediation(None));
+        assert_eq!(summary.kb, None);
+    }
+}
@@ -3,8 +3,12 @@ use clap::Parser;
 use futures::lock::Mutex;
 use futures::{stream, StreamExt};
 use patch_tuesday::cvrf::CVRFDocument;
-use patch_tuesday::{Product, Severity, Vulnerability};
+use patch_tuesday::cvss;
+use patch_tuesday::output::{self, Format};
+use patch_tuesday::{product_lookup, Severity, Vulnerability};
 use reqwest::header;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio;
 
@@ -18,14 +22,13 @@ struct Args {
     #[arg(short, long, default_value_t=Local::now().format("%Y-%b").to_string())]
     date: String,
 
-    /// Product from which to obtain information
-    #[arg(
-        value_enum,
-        short,
-        long,
-        default_value_t = Product::Win10_1809_x64 //"Windows 10 Version 1809 for x64-based Systems"
-    )]
-    product: Product,
+    /// Filter by given text contained in the product name
+    #[arg(short, long)]
+    product: Option<String>,
+
+    /// List all products in the ProductTree for the given month and exit
+    #[arg(long)]
+    list_products: bool,
 
     /// Year(s) from which to obtain information separated by comma
     #[arg(long, conflicts_with = "date", value_delimiter = ',')]
@@ -42,16 +45,78 @@ struct Args {
     /// Filter by given text contained in acknowledgements
     #[arg(long)]
     acknowledgement: Option<String>,
+
+    /// Cross-enrich each CVE with NVD CVSS vector, CWE and reference data
+    #[arg(long)]
+    enrich_nvd: bool,
+
+    /// Filter by CVSS attack vector
+    #[arg(long)]
+    attack_vector: Option<cvss::AttackVector>,
+
+    /// Filter to vulnerabilities that require no user interaction
+    #[arg(long)]
+    no_user_interaction: bool,
+
+    /// Filter by minimum CVSS base score
+    #[arg(long)]
+    min_cvss: Option<f64>,
+
+    /// Output format; auto-detected from --output's extension if not given
+    #[arg(long, value_enum)]
+    format: Option<Format>,
+
+    /// Write output to a file instead of stdout
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// Filter to vulnerabilities remediated by this KB article
+    #[arg(long)]
+    kb: Option<String>,
+
+    /// Filter to vulnerabilities whose fix requires a build newer than this
+    /// (i.e. still unpatched if your build is at or below it)
+    #[arg(long)]
+    min_build: Option<String>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
+    if args.list_products {
+        let response = reqwest::Client::new()
+            .get(format!("{}{}", CVRF_URL, args.date))
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            println!("No Security Update found for {}", args.date);
+            return Ok(());
+        }
+        let cvrf_document = response.json::<CVRFDocument>().await?;
+
+        let mut products: Vec<(&String, &String)> = cvrf_document
+            .product_tree
+            .full_product_name
+            .iter()
+            .map(|product| (&product.product_id, &product.value))
+            .collect();
+        products.sort_by(|a, b| a.1.cmp(b.1));
+        products
+            .iter()
+            .for_each(|(id, name)| println!("{id}: {name}"));
+
+        return Ok(());
+    }
+
     let mut vulns: Vec<Vulnerability>;
+    let products: HashMap<String, String>;
 
     if let Some(year) = args.year {
         let vulns_year = Arc::new(Mutex::new(Vec::<Vulnerability>::new()));
+        let products_year = Arc::new(Mutex::new(HashMap::<String, String>::new()));
         let months: [&str; 12] = [
             "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
         ];
@@ -94,6 +159,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             .iter()
                             .map(|cvrf_vulnerability| Vulnerability::from(cvrf_vulnerability));
                         vulns_year.lock().await.extend(vulns_month);
+                        products_year
+                            .lock()
+                            .await
+                            .extend(product_lookup(&cvrf_document.product_tree));
                     }
                     Ok(Err(e)) => eprintln!("{e}"),
                     Err(e) => eprintln!("Got a tokio::JoinError: {}", e),
@@ -102,6 +171,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .await;
 
         vulns = vulns_year.lock().await.clone();
+        products = products_year.lock().await.clone();
     } else {
         let response = reqwest::Client::new()
             .get(format!("{}{}", CVRF_URL, args.date))
@@ -115,6 +185,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         let cvrf_document = response.json::<CVRFDocument>().await?;
 
+        products = product_lookup(&cvrf_document.product_tree);
         vulns = cvrf_document
             .vulnerability
             .iter()
@@ -147,14 +218,89 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         });
     }
 
-    if args.product != Product::All {
+    if let Some(attack_vector) = args.attack_vector {
+        vulns.retain(|vuln| {
+            vuln.cvss_vector
+                .as_ref()
+                .is_some_and(|vector| vector.attack_vector == attack_vector)
+        });
+    }
+
+    if args.no_user_interaction {
+        vulns.retain(|vuln| {
+            vuln.cvss_vector
+                .as_ref()
+                .is_some_and(|vector| vector.user_interaction == cvss::UserInteraction::None)
+        });
+    }
+
+    if let Some(min_cvss) = args.min_cvss {
+        vulns.retain(|vuln| vuln.cvss.is_some_and(|cvss| cvss >= min_cvss));
+    }
+
+    let matching_ids: Vec<String> = match &args.product {
+        Some(product) => products
+            .iter()
+            .filter(|(_, name)| name.to_lowercase().contains(&product.to_lowercase()))
+            .map(|(id, _)| id.clone())
+            .collect(),
+        None => Vec::new(),
+    };
+    if args.product.is_some() {
         vulns.retain(|vuln| {
             vuln.affected_products
-                .contains(&(args.product as u64).to_string())
+                .iter()
+                .any(|id| matching_ids.contains(id))
         });
     }
 
-    vulns.iter().for_each(|vuln| println!("{vuln}"));
+    vulns
+        .iter_mut()
+        .for_each(|vuln| vuln.scope_remediations(&matching_ids));
+
+    if let Some(kb) = &args.kb {
+        let kb_number = kb.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+        vulns.retain(|vuln| {
+            vuln.remediations
+                .iter()
+                .any(|remediation| remediation.kb.as_deref() == Some(kb_number))
+        });
+    }
+
+    if let Some(min_build) = &args.min_build {
+        vulns.retain(|vuln| {
+            vuln.remediations.iter().any(|remediation| {
+                remediation.fixed_build.as_ref().is_some_and(|build| {
+                    patch_tuesday::compare_builds(build, min_build) == std::cmp::Ordering::Greater
+                })
+            })
+        });
+    }
+
+    // Enrich only the vulns that survived every filter above, since NVD's
+    // rate limit is much stricter than MSRC's and most runs narrow a whole
+    // month down to a handful of CVEs.
+    if args.enrich_nvd {
+        let nvd_client = patch_tuesday::nvd::NvdClient::new();
+        patch_tuesday::nvd::enrich(&nvd_client, &mut vulns).await;
+    }
+
+    vulns
+        .iter_mut()
+        .for_each(|vuln| vuln.resolve_products(&products));
+
+    let format = match (args.format, &args.output) {
+        (Some(format), _) => format,
+        (None, Some(output)) => Format::from_extension(output)?,
+        (None, None) => Format::Text,
+    };
+
+    let rendered = output::render(&vulns, format)?;
+
+    match args.output {
+        Some(output) => std::fs::write(output, rendered)?,
+        None => println!("{rendered}"),
+    }
 
     Ok(())
 }
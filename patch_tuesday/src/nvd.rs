@@ -0,0 +1,175 @@
+// Thin client for the NVD 2.0 REST API, used to hydrate fields MSRC's CVRF
+// feed leaves thin (authoritative CVSS vector, CWE, references).
+use crate::Vulnerability;
+use futures::{stream, StreamExt};
+use reqwest::header;
+use serde::Deserialize;
+
+const NVD_CVE_URL: &str = "https://services.nvd.nist.gov/rest/json/cves/2.0";
+// NVD's public rate limit is much stricter than MSRC's, so we stream fewer
+// requests in flight at a time than PARALLEL_REQUESTS in main.rs.
+const NVD_PARALLEL_REQUESTS: usize = 4;
+
+/// Parameters for a single NVD `cves/2.0` lookup.
+pub struct CveParameters {
+    pub cve_id: String,
+}
+
+impl CveParameters {
+    pub fn new(cve_id: impl Into<String>) -> Self {
+        CveParameters {
+            cve_id: cve_id.into(),
+        }
+    }
+}
+
+/// Small async client around the NVD 2.0 REST API.
+pub struct NvdClient {
+    client: reqwest::Client,
+}
+
+impl Default for NvdClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NvdClient {
+    pub fn new() -> Self {
+        NvdClient {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    pub async fn cve(&self, params: CveParameters) -> Result<Option<NvdCve>, String> {
+        let response = self
+            .client
+            .get(NVD_CVE_URL)
+            .query(&[("cveId", &params.cve_id)])
+            .header(header::ACCEPT, "application/json")
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "NVD lookup failed for {}: {}",
+                params.cve_id,
+                response.status()
+            ));
+        }
+
+        let document = response
+            .json::<NvdResponse>()
+            .await
+            .map_err(|err| err.to_string())?;
+
+        Ok(document.vulnerabilities.into_iter().next().map(|v| v.cve))
+    }
+}
+
+#[derive(Deserialize)]
+struct NvdResponse {
+    vulnerabilities: Vec<NvdVulnerability>,
+}
+
+#[derive(Deserialize)]
+struct NvdVulnerability {
+    cve: NvdCve,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdCve {
+    pub id: String,
+    pub published: String,
+    pub last_modified: String,
+    #[serde(default)]
+    pub metrics: NvdMetrics,
+    #[serde(default)]
+    pub weaknesses: Vec<NvdWeakness>,
+    #[serde(default)]
+    pub references: Vec<NvdReference>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct NvdMetrics {
+    #[serde(default, rename = "cvssMetricV31")]
+    pub cvss_metric_v31: Vec<NvdCvssMetric>,
+    #[serde(default, rename = "cvssMetricV30")]
+    pub cvss_metric_v30: Vec<NvdCvssMetric>,
+}
+
+#[derive(Deserialize)]
+pub struct NvdCvssMetric {
+    #[serde(rename = "cvssData")]
+    pub cvss_data: NvdCvssData,
+}
+
+#[derive(Deserialize)]
+pub struct NvdCvssData {
+    #[serde(rename = "vectorString")]
+    pub vector_string: String,
+    #[serde(rename = "baseScore")]
+    pub base_score: f64,
+}
+
+#[derive(Deserialize)]
+pub struct NvdWeakness {
+    pub description: Vec<NvdLangValue>,
+}
+
+#[derive(Deserialize)]
+pub struct NvdLangValue {
+    pub value: String,
+}
+
+#[derive(Deserialize)]
+pub struct NvdReference {
+    pub url: String,
+}
+
+/// Hydrate `vulns` in place with the NVD CVSS vector, CWE identifiers and
+/// reference URLs for each CVE, streaming lookups with the same
+/// `buffer_unordered` pattern used to pull CVRF documents in main.rs.
+pub async fn enrich(client: &NvdClient, vulns: &mut [Vulnerability]) {
+    let results = stream::iter(vulns.iter().map(|vuln| vuln.cve.clone()))
+        .map(|cve_id| async move {
+            let result = client.cve(CveParameters::new(cve_id.clone())).await;
+            (cve_id, result)
+        })
+        .buffer_unordered(NVD_PARALLEL_REQUESTS)
+        .collect::<Vec<_>>()
+        .await;
+
+    for (cve_id, result) in results {
+        match result {
+            Ok(Some(nvd_cve)) => {
+                if let Some(vuln) = vulns.iter_mut().find(|vuln| vuln.cve == cve_id) {
+                    let cvss_metric = nvd_cve
+                        .metrics
+                        .cvss_metric_v31
+                        .first()
+                        .or_else(|| nvd_cve.metrics.cvss_metric_v30.first());
+                    vuln.nvd_cvss_vector =
+                        cvss_metric.map(|metric| metric.cvss_data.vector_string.clone());
+                    vuln.nvd_published = Some(nvd_cve.published.clone());
+                    vuln.nvd_last_modified = Some(nvd_cve.last_modified.clone());
+                    vuln.cwe = nvd_cve
+                        .weaknesses
+                        .iter()
+                        .flat_map(|weakness| weakness.description.iter())
+                        .map(|lang_value| lang_value.value.clone())
+                        .collect();
+                    vuln.references = nvd_cve
+                        .references
+                        .iter()
+                        .map(|reference| reference.url.clone())
+                        .collect();
+                }
+            }
+            Ok(None) => eprintln!("No NVD entry found for {cve_id}"),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+// Structured output formats for a list of `Vulnerability` records: plain
+// text (the original `Display` block), JSON, CSV, and SARIF 2.1.0 for
+// ingestion by code-scanning dashboards.
+use crate::{Severity, Vulnerability};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::ValueEnum, Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Sarif,
+}
+
+impl Format {
+    /// Infers a format from an `--output` file extension, for when
+    /// `--format` isn't given explicitly.
+    pub fn from_extension(path: &Path) -> Result<Self, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Format::Json),
+            Some("csv") => Ok(Format::Csv),
+            Some("sarif") => Ok(Format::Sarif),
+            Some("txt") => Ok(Format::Text),
+            other => Err(format!(
+                "could not detect an output format from extension {other:?}; pass --format explicitly"
+            )),
+        }
+    }
+}
+
+pub fn render(vulns: &[Vulnerability], format: Format) -> Result<String, String> {
+    match format {
+        Format::Text => Ok(vulns
+            .iter()
+            .map(|vuln| vuln.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")),
+        Format::Json => serde_json::to_string_pretty(vulns).map_err(|err| err.to_string()),
+        Format::Csv => render_csv(vulns),
+        Format::Sarif => {
+            serde_json::to_string_pretty(&to_sarif(vulns)).map_err(|err| err.to_string())
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    cve: &'a str,
+    title: &'a str,
+    severity: String,
+    cvss: Option<f64>,
+    impact: &'a str,
+    public: bool,
+    exploited: bool,
+    affected_products: String,
+    cwe: String,
+    references: String,
+}
+
+fn render_csv(vulns: &[Vulnerability]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    for vuln in vulns {
+        writer
+            .serialize(CsvRow {
+                cve: &vuln.cve,
+                title: &vuln.title,
+                severity: vuln.severity.to_string(),
+                cvss: vuln.cvss,
+                impact: &vuln.impact,
+                public: vuln.public,
+                exploited: vuln.exploited,
+                affected_products: vuln.affected_products.join("; "),
+                cwe: vuln.cwe.join("; "),
+                references: vuln.references.join("; "),
+            })
+            .map_err(|err| err.to_string())?;
+    }
+    let bytes = writer.into_inner().map_err(|err| err.to_string())?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+struct SarifDriver {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: &'static str,
+    message: SarifMessage,
+    properties: SarifProperties,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct SarifProperties {
+    cvss: Option<f64>,
+    exploited: bool,
+    public: bool,
+}
+
+fn sarif_level(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical | Severity::High => "error",
+        Severity::Moderate | Severity::Medium => "warning",
+        _ => "note",
+    }
+}
+
+fn to_sarif(vulns: &[Vulnerability]) -> SarifLog {
+    let results = vulns
+        .iter()
+        .map(|vuln| SarifResult {
+            rule_id: vuln.cve.clone(),
+            level: sarif_level(&vuln.severity),
+            message: SarifMessage {
+                text: vuln.title.clone(),
+            },
+            properties: SarifProperties {
+                cvss: vuln.cvss,
+                exploited: vuln.exploited,
+                public: vuln.public,
+            },
+        })
+        .collect();
+
+    SarifLog {
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        version: "2.1.0",
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "patch_tuesday",
+                },
+            },
+            results,
+        }],
+    }
+}